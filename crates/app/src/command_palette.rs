@@ -0,0 +1,185 @@
+use std::rc::Rc;
+
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, px, Action, DismissEvent, EventEmitter, FocusHandle,
+    FocusableView, InteractiveElement, IntoElement, KeyBinding, KeyContext, ParentElement, Render,
+    SharedString, Styled, View, ViewContext, VisualContext as _,
+};
+use ui::{h_flex, input::TextInput, theme::ActiveTheme as _, v_flex};
+
+use crate::fuzzy::fuzzy_match;
+
+actions!(command_palette, [Confirm, SelectNext, SelectPrev, Dismiss]);
+
+/// The palette's own navigation keys, scoped to [`CommandPalette`] via its
+/// `"CommandPalette"` key context. These are folded into every keymap
+/// reload (see [`crate::keymap`]) rather than bound once at startup, so a
+/// user-keymap reload's clear-then-rebind doesn't drop them.
+pub fn key_bindings() -> Vec<KeyBinding> {
+    vec![
+        KeyBinding::new("up", SelectPrev, Some("CommandPalette")),
+        KeyBinding::new("down", SelectNext, Some("CommandPalette")),
+        KeyBinding::new("enter", Confirm, Some("CommandPalette")),
+        KeyBinding::new("escape", Dismiss, Some("CommandPalette")),
+    ]
+}
+
+/// A single searchable entry: a human-readable label and the action it
+/// dispatches when activated (`Quit`, `Open`, `SelectLocale`, ...). No
+/// command activates a story panel in the dock.
+pub struct Command {
+    pub label: SharedString,
+    pub action: Box<dyn Action>,
+}
+
+impl Command {
+    pub fn new(label: impl Into<SharedString>, action: impl Action) -> Self {
+        Self {
+            label: label.into(),
+            action: Box::new(action),
+        }
+    }
+}
+
+pub struct CommandPalette {
+    focus_handle: FocusHandle,
+    query_input: View<TextInput>,
+    commands: Rc<Vec<Command>>,
+    selected_ix: usize,
+}
+
+impl CommandPalette {
+    pub fn new(commands: Rc<Vec<Command>>, cx: &mut ViewContext<Self>) -> Self {
+        let query_input = cx.new_view(|cx| TextInput::new(cx).placeholder("Search actions..."));
+        cx.focus_view(&query_input);
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            query_input,
+            commands,
+            selected_ix: 0,
+        }
+    }
+
+    /// Commands matching the current query, sorted by descending fuzzy
+    /// score, each paired with the matched character positions to bold.
+    fn matches(&self, cx: &ViewContext<Self>) -> Vec<(usize, isize, Vec<usize>)> {
+        let query = self.query_input.read(cx).text(cx);
+        let mut matches: Vec<(usize, isize, Vec<usize>)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, command)| {
+                fuzzy_match(&command.label, &query).map(|m| (ix, m.score, m.positions))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.1.cmp(&a.1));
+        matches
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        let len = self.matches(cx).len();
+        if len > 0 {
+            self.selected_ix = (self.selected_ix + 1) % len;
+            cx.notify();
+        }
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        let len = self.matches(cx).len();
+        if len > 0 {
+            self.selected_ix = (self.selected_ix + len - 1) % len;
+            cx.notify();
+        }
+    }
+
+    fn confirm_selected(&mut self, cx: &mut ViewContext<Self>) {
+        let matches = self.matches(cx);
+        // `selected_ix` isn't reset when the query narrows the match list, so
+        // it can point past the end here even though `render` clamps its own
+        // copy before using it for highlighting; clamp this one too rather
+        // than silently dismissing without dispatching anything.
+        let selected_ix = self.selected_ix.min(matches.len().saturating_sub(1));
+        if let Some((command_ix, ..)) = matches.get(selected_ix).cloned() {
+            let action = self.commands[command_ix].action.boxed_clone();
+            cx.dispatch_action(action);
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
+        self.confirm_selected(cx);
+    }
+
+    fn dismiss(&mut self, _: &Dismiss, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for CommandPalette {}
+
+impl FocusableView for CommandPalette {
+    fn focus_handle(&self, _: &gpui::AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for CommandPalette {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let mut key_context = KeyContext::new_with_defaults();
+        key_context.add("CommandPalette");
+
+        let matches = self.matches(cx);
+        let selected_ix = self.selected_ix.min(matches.len().saturating_sub(1));
+
+        v_flex()
+            .key_context(key_context)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::dismiss))
+            .w(px(480.))
+            .gap_2()
+            .p_2()
+            .child(self.query_input.clone())
+            .child(
+                v_flex()
+                    .max_h(px(360.))
+                    .children(matches.into_iter().enumerate().map(
+                        |(row_ix, (command_ix, _score, positions))| {
+                            let command = &self.commands[command_ix];
+                            let selected = row_ix == selected_ix;
+                            h_flex()
+                                .id(("command-palette-item", row_ix))
+                                .px_2()
+                                .py_1()
+                                .rounded_md()
+                                .when(selected, |this| this.bg(cx.theme().accent))
+                                .child(highlighted_label(&command.label, &positions))
+                                .on_click(cx.listener(move |this, _, cx| {
+                                    this.selected_ix = row_ix;
+                                    this.confirm_selected(cx);
+                                }))
+                        },
+                    )),
+            )
+    }
+}
+
+/// Renders `label` with the characters at `positions` bolded, so matched
+/// query characters stand out in the list.
+fn highlighted_label(label: &SharedString, positions: &[usize]) -> impl IntoElement {
+    use std::collections::HashSet;
+
+    let matched: HashSet<usize> = positions.iter().copied().collect();
+
+    h_flex().children(label.chars().enumerate().map(|(ix, ch)| {
+        let span = div().child(ch.to_string());
+        if matched.contains(&ix) {
+            span.font_weight(gpui::FontWeight::BOLD)
+        } else {
+            span
+        }
+    }))
+}
@@ -0,0 +1,130 @@
+/// A scored fuzzy match of a query against some candidate string.
+pub struct FuzzyMatch {
+    /// `char` indices (not byte offsets) into `candidate` that matched a
+    /// query character, in order, for highlighting. Callers indexing
+    /// `candidate` with these need `candidate.chars()`, not byte slicing.
+    pub positions: Vec<usize>,
+    pub score: isize,
+}
+
+const BASE_SCORE: isize = 16;
+const CONSECUTIVE_BONUS: isize = 8;
+const WORD_BOUNDARY_BONUS: isize = 12;
+const LEADING_BONUS: isize = 6;
+const GAP_PENALTY: isize = 1;
+
+/// Scores `candidate` against `query` using subsequence fuzzy matching:
+/// `candidate` matches only if every character of `query` appears in order
+/// within it (case-insensitively). Picks the best-scoring alignment via
+/// dynamic programming, rewarding consecutive matches, word-boundary
+/// matches and a match at the very first character, and penalizing gaps
+/// between consecutive matched characters.
+///
+/// Returns `None` if `query` is not a subsequence of `candidate`. An empty
+/// `query` always matches with a score of `0` and no highlighted positions.
+pub fn fuzzy_match(candidate: &str, query: &str) -> Option<FuzzyMatch> {
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            positions: Vec::new(),
+            score: 0,
+        });
+    }
+
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let query_chars: Vec<char> = query.chars().collect();
+    let n = candidate_chars.len();
+    let m = query_chars.len();
+    if m > n {
+        return None;
+    }
+
+    let is_word_boundary = |i: usize| -> bool {
+        if i == 0 {
+            return true;
+        }
+        let prev = candidate_chars[i - 1];
+        if matches!(prev, ' ' | '_' | '-' | '.') {
+            return true;
+        }
+        prev.is_lowercase() && candidate_chars[i].is_uppercase()
+    };
+
+    let char_score = |ci: usize| -> isize {
+        let mut score = BASE_SCORE;
+        if is_word_boundary(ci) {
+            score += WORD_BOUNDARY_BONUS;
+        }
+        if ci == 0 {
+            score += LEADING_BONUS;
+        }
+        score
+    };
+
+    // `best[ci]` / `back[ci]` are the DP row for the query char currently
+    // being matched: the best score (and predecessor candidate index) for
+    // an alignment of `query[..=qi]` ending exactly at candidate index `ci`.
+    //
+    // Each candidate predecessor `cj < ci` is scanned directly (rather than
+    // tracked via a running best) so the consecutive-match bonus and the
+    // gap penalty can be applied per-predecessor, as part of choosing the
+    // best one, instead of being folded into a single O(1) running value
+    // that can't tell them apart.
+    let mut prev_best: Vec<Option<isize>> = vec![None; n];
+    let mut rows_back: Vec<Vec<Option<usize>>> = Vec::with_capacity(m);
+
+    for (qi, &qc) in query_chars.iter().enumerate() {
+        let mut best: Vec<Option<isize>> = vec![None; n];
+        let mut back: Vec<Option<usize>> = vec![None; n];
+
+        for ci in 0..n {
+            if candidate_chars[ci].to_ascii_lowercase() != qc.to_ascii_lowercase() {
+                continue;
+            }
+
+            let score = char_score(ci);
+
+            if qi == 0 {
+                best[ci] = Some(score);
+                continue;
+            }
+
+            for cj in 0..ci {
+                let Some(prev_score) = prev_best[cj] else {
+                    continue;
+                };
+
+                let consecutive = cj + 1 == ci;
+                let total = if consecutive {
+                    prev_score + score + CONSECUTIVE_BONUS
+                } else {
+                    prev_score + score - GAP_PENALTY * (ci - cj - 1) as isize
+                };
+
+                if best[ci].is_none_or(|current| total > current) {
+                    best[ci] = Some(total);
+                    back[ci] = Some(cj);
+                }
+            }
+        }
+
+        rows_back.push(back);
+        prev_best = best;
+    }
+
+    let (end, score) = prev_best
+        .iter()
+        .enumerate()
+        .filter_map(|(ci, score)| score.map(|s| (ci, s)))
+        .max_by_key(|&(_, s)| s)?;
+
+    let mut positions = Vec::with_capacity(m);
+    let mut idx = Some(end);
+    for qi in (0..m).rev() {
+        let ci = idx?;
+        positions.push(ci);
+        idx = rows_back[qi][ci];
+    }
+    positions.reverse();
+
+    Some(FuzzyMatch { positions, score })
+}
@@ -0,0 +1,135 @@
+use std::{path::PathBuf, rc::Rc, time::Duration};
+
+use anyhow::{Context as _, Result};
+use gpui::{AppContext, Global, KeyBinding, Timer};
+use serde::Deserialize;
+
+use crate::{assets::Assets, command_palette, paths::config_dir};
+
+const KEYMAP_FILE: &str = "keymap.json";
+
+/// The callback that (re-)registers every binding this app doesn't own
+/// itself — `ui::init`/`story::init`'s component keybindings (e.g.
+/// `TextInput` editing keys) — passed in by [`init`]. `cx.clear_key_bindings`
+/// clears the *whole* app's keymap, component bindings included, so a
+/// reload has to re-run this right after clearing or those component
+/// bindings are gone for the rest of the process.
+struct ComponentBindings(Rc<dyn Fn(&mut AppContext)>);
+
+impl Global for ComponentBindings {}
+
+/// One `keymap.json` entry: a keystroke bound to an action, optionally
+/// scoped to a context predicate and carrying a JSON payload for
+/// parameterized actions (e.g. `SelectLocale`).
+#[derive(Debug, Clone, Deserialize)]
+struct KeymapEntry {
+    /// Context predicate the binding is scoped to, e.g. `"CommandPalette"`.
+    /// `None` binds globally.
+    context: Option<String>,
+    keystroke: String,
+    /// Namespaced action name, as registered by the `actions!` /
+    /// `impl_actions!` call it came from, e.g. `"workspace::ToggleCommandPalette"`.
+    action: String,
+    #[serde(default)]
+    args: Option<serde_json::Value>,
+}
+
+/// Loads `keymap.json` bundled through [`Assets`] (served from
+/// `crates/app/assets/keymap.json`), overlaid with an optional user
+/// override at `<config_dir>/keymap.json`, and binds the result. The user
+/// file is then watched so edits take effect without restarting the app.
+///
+/// `register_component_bindings` is whatever registers this app's
+/// component-level bindings (currently `ui::init`/`story::init`, passed in
+/// by the app's `init` in `main.rs`) — see [`ComponentBindings`]
+/// for why it has to live here and get re-run on every reload, not just
+/// called once at startup.
+pub fn init(cx: &mut AppContext, register_component_bindings: impl Fn(&mut AppContext) + 'static) {
+    cx.set_global(ComponentBindings(Rc::new(register_component_bindings)));
+    load_and_bind(cx);
+    watch_user_keymap(cx);
+}
+
+/// Rebuilds the entire keymap from scratch — component bindings, the
+/// palette's own navigation keys, and every resolved `keymap.json` entry —
+/// and replaces whatever was previously bound. Doing a full clear-then-rebind
+/// (rather than appending) is what makes reloading on a user-keymap edit
+/// safe: appending would pile up duplicate bindings on every edit, and never
+/// unbind one the user removed. The tradeoff is that `clear_key_bindings`
+/// clears bindings this module doesn't own too, so they have to be
+/// reconstructed via `ComponentBindings` every time, not just registered once.
+fn load_and_bind(cx: &mut AppContext) {
+    let register_component_bindings = cx.global::<ComponentBindings>().0.clone();
+
+    let mut entries = load_builtin_keymap().unwrap_or_else(|err| {
+        log::error!("failed to load default keymap: {}", err);
+        Vec::new()
+    });
+    entries.extend(load_user_keymap().unwrap_or_default());
+
+    let mut bindings = command_palette::key_bindings();
+    bindings.extend(entries.iter().filter_map(|entry| match resolve(entry, cx) {
+        Ok(binding) => Some(binding),
+        Err(err) => {
+            log::error!("invalid keymap entry for {:?}: {}", entry.keystroke, err);
+            None
+        }
+    }));
+
+    cx.clear_key_bindings();
+    register_component_bindings(cx);
+    cx.bind_keys(bindings);
+}
+
+fn resolve(entry: &KeymapEntry, cx: &AppContext) -> Result<KeyBinding> {
+    let action = cx
+        .build_action(&entry.action, entry.args.clone())
+        .with_context(|| format!("unknown action {:?}", entry.action))?;
+
+    KeyBinding::load(&entry.keystroke, action, entry.context.as_deref(), None)
+        .with_context(|| format!("invalid keystroke {:?}", entry.keystroke))
+}
+
+fn load_builtin_keymap() -> Result<Vec<KeymapEntry>> {
+    let Some(bytes) = Assets.load(KEYMAP_FILE)? else {
+        return Ok(Vec::new());
+    };
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn user_keymap_path() -> PathBuf {
+    config_dir().join(KEYMAP_FILE)
+}
+
+fn load_user_keymap() -> Result<Vec<KeymapEntry>> {
+    let path = user_keymap_path();
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let json = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Polls the user keymap file's mtime and reloads+rebinds the whole keymap
+/// whenever it changes.
+fn watch_user_keymap(cx: &mut AppContext) {
+    cx.spawn(|mut cx| async move {
+        let path = user_keymap_path();
+        let mut last_modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+        loop {
+            Timer::after(Duration::from_secs(1)).await;
+
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            if modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            if cx.update(|cx| load_and_bind(cx)).is_err() {
+                return;
+            }
+        }
+    })
+    .detach();
+}
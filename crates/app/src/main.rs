@@ -3,18 +3,31 @@ use std::sync::Arc;
 use anyhow::Result;
 use app_state::AppState;
 use assets::Assets;
-use gpui::{actions, App, AppContext, KeyBinding, Menu, MenuItem};
+use gpui::{actions, App, AppContext, Menu, MenuItem};
 
 mod app_state;
 mod assets;
+mod command_palette;
+mod fuzzy;
+mod keymap;
+mod panel_history;
+mod paths;
+mod session;
 mod story_workspace;
+mod theme_registry;
 
 actions!(main_menu, [Quit]);
 
 fn init(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
+    theme_registry::ThemeRegistry::init(cx);
+    session::SessionStore::init(cx);
+
     story_workspace::init(app_state.clone(), cx);
 
-    cx.bind_keys([KeyBinding::new("cmd-q", Quit, None)]);
+    keymap::init(cx, |cx| {
+        ui::init(cx);
+        story::init(cx);
+    });
 
     Ok(())
 }
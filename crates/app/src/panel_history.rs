@@ -0,0 +1,59 @@
+use gpui::FocusHandle;
+
+/// Back/forward navigation stack over recently focused dock panels, in the
+/// style of a browser's history: focusing a panel records it, navigating
+/// back/forward steps through older/newer entries, and focusing a genuinely
+/// new panel after going back discards the abandoned forward entries.
+///
+/// Entries are keyed by [`FocusHandle`] rather than panel title. The dock
+/// crate in this tree exposes no way to look a panel up by title or to
+/// enumerate the panels it currently holds, so history can't be driven by
+/// asking the dock area to jump to a name; it can only replay handles this
+/// process has actually seen receive focus, via the same `on_focus_in`
+/// bubbling `StoryWorkspace` already listens to.
+#[derive(Debug, Default)]
+pub struct PanelHistory {
+    entries: Vec<FocusHandle>,
+    /// Index of the currently-focused panel within `entries`, if any.
+    current: Option<usize>,
+}
+
+impl PanelHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `handle` as the newly-focused panel. A no-op if it's already
+    /// the current entry. Otherwise drops any forward history past the
+    /// current entry and appends `handle` as the new current one.
+    pub fn record(&mut self, handle: FocusHandle) {
+        if let Some(ix) = self.current {
+            if self.entries[ix] == handle {
+                return;
+            }
+            self.entries.truncate(ix + 1);
+        }
+        self.entries.push(handle);
+        self.current = Some(self.entries.len() - 1);
+    }
+
+    /// Moves to the entry before the current one, returning its handle.
+    pub fn navigate_back(&mut self) -> Option<FocusHandle> {
+        let pos = self.current?;
+        if pos == 0 {
+            return None;
+        }
+        self.current = Some(pos - 1);
+        Some(self.entries[pos - 1].clone())
+    }
+
+    /// Moves to the entry after the current one, returning its handle.
+    pub fn navigate_forward(&mut self) -> Option<FocusHandle> {
+        let pos = self.current?;
+        if pos + 1 >= self.entries.len() {
+            return None;
+        }
+        self.current = Some(pos + 1);
+        Some(self.entries[pos + 1].clone())
+    }
+}
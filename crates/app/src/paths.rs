@@ -0,0 +1,9 @@
+use std::path::PathBuf;
+
+/// The directory where this app keeps user-editable configuration, e.g.
+/// `~/.config/gpuicom-dock` on Linux.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("gpuicom-dock")
+}
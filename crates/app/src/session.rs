@@ -0,0 +1,155 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::Result;
+use gpui::{px, AppContext, Bounds, Global, WindowBounds};
+use serde::{Deserialize, Serialize};
+use ui::{dock::DockAreaState, theme::ThemeMode};
+
+use crate::paths::config_dir;
+
+const SESSIONS_FILE: &str = "sessions.json";
+
+/// Plain, serializable stand-in for [`WindowBounds`], which (being a wrapper
+/// around gpui's platform `Bounds<Pixels>`) isn't itself guaranteed
+/// serializable. Holds the same data as raw floats and a mode tag so a
+/// window's bounds can round-trip through `sessions.json`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SavedWindowBounds {
+    mode: SavedWindowMode,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum SavedWindowMode {
+    Windowed,
+    Maximized,
+    Fullscreen,
+}
+
+impl From<WindowBounds> for SavedWindowBounds {
+    fn from(bounds: WindowBounds) -> Self {
+        let (mode, bounds) = match bounds {
+            WindowBounds::Windowed(b) => (SavedWindowMode::Windowed, b),
+            WindowBounds::Maximized(b) => (SavedWindowMode::Maximized, b),
+            WindowBounds::Fullscreen(b) => (SavedWindowMode::Fullscreen, b),
+        };
+        Self {
+            mode,
+            x: f32::from(bounds.origin.x),
+            y: f32::from(bounds.origin.y),
+            width: f32::from(bounds.size.width),
+            height: f32::from(bounds.size.height),
+        }
+    }
+}
+
+impl From<SavedWindowBounds> for WindowBounds {
+    fn from(saved: SavedWindowBounds) -> Self {
+        let bounds = Bounds {
+            origin: gpui::point(px(saved.x), px(saved.y)),
+            size: gpui::size(px(saved.width), px(saved.height)),
+        };
+        match saved.mode {
+            SavedWindowMode::Windowed => WindowBounds::Windowed(bounds),
+            SavedWindowMode::Maximized => WindowBounds::Maximized(bounds),
+            SavedWindowMode::Fullscreen => WindowBounds::Fullscreen(bounds),
+        }
+    }
+}
+
+/// Everything needed to restore one named workspace arrangement: the dock
+/// layout, the window's last bounds, and the active theme/locale.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub layout: DockAreaState,
+    pub window_bounds: Option<SavedWindowBounds>,
+    pub theme_mode: ThemeMode,
+    pub locale: String,
+}
+
+/// Named workspace sessions persisted under the platform config directory,
+/// replacing the single anonymous `layout.json` this app used to write.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SessionStore {
+    sessions: HashMap<String, Session>,
+    active: Option<String>,
+}
+
+impl Global for SessionStore {}
+
+impl SessionStore {
+    fn path() -> PathBuf {
+        config_dir().join(SESSIONS_FILE)
+    }
+
+    /// Loads the store from disk and installs it as a global, so callers
+    /// (e.g. `new_local`) can read the active session through
+    /// [`Self::global`] before any window exists.
+    pub fn init(cx: &mut AppContext) {
+        cx.set_global(Self::load_from_disk());
+    }
+
+    /// Reads the store from disk, or an empty store if it doesn't exist yet.
+    pub fn load_from_disk() -> Self {
+        Self::try_load().unwrap_or_else(|err| {
+            log::debug!("no existing sessions to load: {}", err);
+            Self::default()
+        })
+    }
+
+    fn try_load() -> Result<Self> {
+        let json = std::fs::read_to_string(Self::path())?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::create_dir_all(config_dir())?;
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(Self::path(), json)?;
+        Ok(())
+    }
+
+    pub fn global(cx: &AppContext) -> &Self {
+        cx.global::<Self>()
+    }
+
+    /// Names of saved sessions, sorted for stable menu ordering.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.sessions.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Session> {
+        self.sessions.get(name)
+    }
+
+    pub fn active(&self) -> Option<&Session> {
+        self.active.as_deref().and_then(|name| self.sessions.get(name))
+    }
+
+    pub fn active_name(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    /// Saves `session` under `name`, making it the active session.
+    pub fn upsert(&mut self, name: impl Into<String>, session: Session) {
+        let name = name.into();
+        self.sessions.insert(name.clone(), session);
+        self.active = Some(name);
+    }
+
+    pub fn set_active(&mut self, name: impl Into<String>) {
+        self.active = Some(name.into());
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.sessions.remove(name);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use gpui::*;
 use prelude::FluentBuilder as _;
 use serde::Deserialize;
-use std::{sync::Arc, time::Duration};
+use std::{rc::Rc, sync::Arc, time::Duration};
 use story::{
     AccordionStory, ButtonStory, CalendarStory, DropdownStory, IconStory, ImageStory, InputStory,
     ListStory, ModalStory, PopupStory, ProgressStory, ResizableStory, ScrollableStory,
@@ -13,12 +13,17 @@ use ui::{
     color_picker::{ColorPicker, ColorPickerEvent},
     dock::{DockArea, DockAreaState, DockEvent, DockItem, PanelView},
     h_flex,
+    input::TextInput,
     popup_menu::PopupMenuExt,
     theme::{ActiveTheme, Theme},
-    ContextModal, IconName, Root, Sizable, TitleBar,
+    v_flex, ContextModal, IconName, Root, Sizable, TitleBar,
 };
 
 use crate::app_state::AppState;
+use crate::command_palette::{Command, CommandPalette};
+use crate::panel_history::PanelHistory;
+use crate::session::{SavedWindowBounds, Session, SessionStore};
+use crate::theme_registry::{ThemeRegistry, ThemeRegistryExt as _};
 
 const MAIN_DOCK_AREA: DockAreaTab = DockAreaTab {
     id: "main-dock",
@@ -28,22 +33,53 @@ const MAIN_DOCK_AREA: DockAreaTab = DockAreaTab {
 #[derive(Clone, PartialEq, Eq, Deserialize)]
 struct SelectLocale(SharedString);
 
-impl_actions!(locale_switcher, [SelectLocale]);
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+struct SelectTheme(SharedString);
 
-actions!(workspace, [Open, CloseWindow]);
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+struct SwitchLayout(SharedString);
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+struct DeleteLayout(SharedString);
 
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+struct ConfirmSaveLayout(SharedString);
+
+impl_actions!(locale_switcher, [SelectLocale]);
+impl_actions!(theme_switcher, [SelectTheme]);
+impl_actions!(
+    workspace,
+    [SwitchLayout, DeleteLayout, ConfirmSaveLayout]
+);
+
+actions!(
+    workspace,
+    [
+        Open,
+        CloseWindow,
+        ToggleCommandPalette,
+        SaveLayoutAs,
+        NavigateBack,
+        NavigateForward
+    ]
+);
+
+/// Registers the workspace's own top-level actions. Component-level
+/// bindings (`ui::init`/`story::init`) are registered by `keymap::init`
+/// instead, since that module has to re-run them on every keymap reload
+/// anyway — see `ComponentBindings` in `keymap.rs`.
 pub fn init(_app_state: Arc<AppState>, cx: &mut AppContext) {
     cx.on_action(|_action: &Open, _cx: &mut AppContext| {});
-
-    ui::init(cx);
-    story::init(cx);
 }
 
 pub struct StoryWorkspace {
     theme_color: Option<Hsla>,
     dock_area: View<DockArea>,
     locale_selector: View<LocaleSelector>,
+    theme_selector: View<ThemeSelector>,
+    layouts_selector: View<LayoutsSelector>,
     theme_color_picker: View<ColorPicker>,
+    panel_history: PanelHistory,
     last_layout_state: Option<DockAreaState>,
     _save_layout_task: Option<Task<()>>,
 }
@@ -64,7 +100,7 @@ impl StoryWorkspace {
             cx.new_view(|cx| DockArea::new(MAIN_DOCK_AREA.id, Some(MAIN_DOCK_AREA.version), cx));
         let weak_dock_area = dock_area.downgrade();
 
-        match Self::load_layout(dock_area.clone(), cx) {
+        match Self::load_active_session(dock_area.clone(), cx) {
             Ok(_) => {
                 println!("load layout success");
             }
@@ -83,15 +119,19 @@ impl StoryWorkspace {
             let dock_area = dock_area.clone();
             move |cx| {
                 let state = dock_area.read(cx).dump(cx);
+                let window_bounds = cx.window_bounds();
+                let (name, session) = Self::session_snapshot(cx, state, Some(window_bounds));
                 cx.background_executor().spawn(async move {
-                    // Save layout before quitting
-                    Self::save_state(&state).unwrap();
+                    // Save the session before quitting
+                    let _ = Self::persist_session_to_disk(name, session);
                 })
             }
         })
         .detach();
 
         let locale_selector = cx.new_view(LocaleSelector::new);
+        let theme_selector = cx.new_view(ThemeSelector::new);
+        let layouts_selector = cx.new_view(LayoutsSelector::new);
 
         let theme_color_picker = cx.new_view(|cx| {
             let mut picker = ColorPicker::new("theme-color-picker", cx)
@@ -115,7 +155,10 @@ impl StoryWorkspace {
             theme_color: None,
             dock_area,
             locale_selector,
+            theme_selector,
+            layouts_selector,
             theme_color_picker,
+            panel_history: PanelHistory::new(),
             last_layout_state: None,
             _save_layout_task: None,
         }
@@ -140,6 +183,136 @@ impl StoryWorkspace {
         self.set_theme_color(self.theme_color, cx);
     }
 
+    /// Every action the command palette can search: `Quit`/`Open`/`CloseWindow`,
+    /// one entry per locale, and one per registered theme.
+    ///
+    /// There's deliberately no "jump to story panel" entry: doing that
+    /// correctly needs the dock area to look a panel up by title or
+    /// enumerate what it's currently holding, and `ui::dock::DockArea` in
+    /// this tree exposes neither, so a command that appeared to do this
+    /// would silently do nothing instead.
+    fn build_commands(cx: &ViewContext<Self>) -> Vec<Command> {
+        let mut commands = vec![
+            Command::new("Quit", crate::Quit),
+            Command::new("Open", Open),
+            Command::new("Close Window", CloseWindow),
+        ];
+
+        for locale in ["en", "zh-CN"] {
+            commands.push(Command::new(
+                format!("Locale: {}", locale),
+                SelectLocale(locale.into()),
+            ));
+        }
+
+        for theme in ThemeRegistry::global(cx).themes() {
+            commands.push(Command::new(
+                format!("Theme: {}", theme.name),
+                SelectTheme(theme.name.clone()),
+            ));
+        }
+
+        commands
+    }
+
+    fn toggle_command_palette(&mut self, _: &ToggleCommandPalette, cx: &mut ViewContext<Self>) {
+        let commands = Rc::new(Self::build_commands(cx));
+        cx.open_modal(move |modal, cx| {
+            let commands = commands.clone();
+            modal
+                .show_close(true)
+                .child(cx.new_view(|cx| CommandPalette::new(commands, cx)))
+        });
+    }
+
+    /// Handles `SelectLocale` dispatched from anywhere that isn't a
+    /// descendant of [`LocaleSelector`] (chiefly the command palette, whose
+    /// modal sits outside that subtree so `LocaleSelector::on_select_locale`
+    /// never sees the action).
+    fn select_locale(&mut self, action: &SelectLocale, cx: &mut ViewContext<Self>) {
+        ui::set_locale(&action.0);
+        cx.refresh();
+    }
+
+    /// Same as [`Self::select_locale`], but for `SelectTheme` dispatched
+    /// from outside [`ThemeSelector`]'s subtree.
+    fn select_theme(&mut self, action: &SelectTheme, cx: &mut ViewContext<Self>) {
+        let Some(definition) = ThemeRegistry::global(cx).get(&action.0).cloned() else {
+            return;
+        };
+        cx.global_mut::<Theme>().apply_theme(&definition);
+        cx.refresh();
+    }
+
+    /// Records whichever dock panel just gained focus, keyed by its own
+    /// [`FocusHandle`] rather than a title (see [`PanelHistory`] for why).
+    /// Focus events that don't carry a newly-focused handle (a blur, or one
+    /// that didn't land on a dock panel) are ignored.
+    fn on_panel_focus_in(&mut self, event: &FocusEvent, _cx: &mut ViewContext<Self>) {
+        if let Some(handle) = event.focused.clone() {
+            self.panel_history.record(handle);
+        }
+    }
+
+    fn navigate_back(&mut self, _: &NavigateBack, cx: &mut ViewContext<Self>) {
+        if let Some(handle) = self.panel_history.navigate_back() {
+            cx.focus(&handle);
+        }
+    }
+
+    fn navigate_forward(&mut self, _: &NavigateForward, cx: &mut ViewContext<Self>) {
+        if let Some(handle) = self.panel_history.navigate_forward() {
+            cx.focus(&handle);
+        }
+    }
+
+    fn open_save_layout_modal(&mut self, _: &SaveLayoutAs, cx: &mut ViewContext<Self>) {
+        cx.open_modal(move |modal, cx| {
+            modal
+                .title("Save Layout As")
+                .show_close(true)
+                .child(cx.new_view(SaveLayoutNameModal::new))
+        });
+    }
+
+    fn switch_layout(&mut self, action: &SwitchLayout, cx: &mut ViewContext<Self>) {
+        let Some(session) = SessionStore::global(cx).get(&action.0).cloned() else {
+            return;
+        };
+
+        Theme::change(session.theme_mode, cx);
+        ui::set_locale(&session.locale);
+
+        self.dock_area.update(cx, |dock_area, cx| {
+            if let Err(err) = dock_area.load(session.layout, cx) {
+                log::error!("failed to load layout {:?}: {}", action.0, err);
+            }
+        });
+
+        cx.update_global::<SessionStore, _>(|store, _| store.set_active(action.0.to_string()));
+        let _ = SessionStore::global(cx).save();
+        cx.refresh();
+    }
+
+    fn delete_layout(&mut self, action: &DeleteLayout, cx: &mut ViewContext<Self>) {
+        cx.update_global::<SessionStore, _>(|store, _| store.remove(&action.0));
+        let _ = SessionStore::global(cx).save();
+        cx.refresh();
+    }
+
+    fn confirm_save_layout(&mut self, action: &ConfirmSaveLayout, cx: &mut ViewContext<Self>) {
+        let state = self.dock_area.read(cx).dump(cx);
+        let window_bounds = cx.window_bounds();
+        let (_, mut session) = Self::session_snapshot(cx, state, Some(window_bounds));
+        session.locale = ui::locale().to_string();
+
+        cx.update_global::<SessionStore, _>(|store, _| {
+            store.upsert(action.0.to_string(), session);
+        });
+        let _ = SessionStore::global(cx).save();
+        cx.refresh();
+    }
+
     fn save_layout(&mut self, dock_area: View<DockArea>, cx: &mut ViewContext<Self>) {
         self._save_layout_task = Some(cx.spawn(|this, mut cx| async move {
             Timer::after(Duration::from_secs(10)).await;
@@ -153,7 +326,12 @@ impl StoryWorkspace {
                     return;
                 }
 
-                Self::save_state(&state).unwrap();
+                println!("Save layout...");
+                let window_bounds = cx.window_bounds();
+                let (name, session) = Self::session_snapshot(cx, state.clone(), Some(window_bounds));
+                if let Ok(store) = Self::persist_session_to_disk(name, session) {
+                    cx.set_global(store);
+                }
                 let _ = this.update(cx, |this, _| {
                     this.last_layout_state = Some(state);
                 });
@@ -161,21 +339,50 @@ impl StoryWorkspace {
         }));
     }
 
-    fn save_state(state: &DockAreaState) -> Result<()> {
-        println!("Save layout...");
-        let json = serde_json::to_string_pretty(state)?;
-        std::fs::write("layout.json", json)?;
-        Ok(())
+    /// Snapshots everything a [`Session`] needs from the current window:
+    /// the given layout, the currently-active session's name (or
+    /// `"default"` if none is named yet), the theme mode and the locale.
+    fn session_snapshot(
+        cx: &AppContext,
+        layout: DockAreaState,
+        window_bounds: Option<WindowBounds>,
+    ) -> (String, Session) {
+        let name = SessionStore::global(cx)
+            .active_name()
+            .map(str::to_string)
+            .unwrap_or_else(|| "default".to_string());
+
+        let session = Session {
+            layout,
+            window_bounds: window_bounds.map(SavedWindowBounds::from),
+            theme_mode: cx.global::<Theme>().mode,
+            locale: ui::locale().to_string(),
+        };
+
+        (name, session)
     }
 
-    fn load_layout(dock_area: View<DockArea>, cx: &mut WindowContext) -> Result<()> {
-        let fname = "layout.json";
-        let json = std::fs::read_to_string(fname)?;
-        let state = serde_json::from_str::<DockAreaState>(&json)?;
+    /// Re-reads the session store from disk, upserts `session` under
+    /// `name`, and writes it back, returning the merged store so the
+    /// in-memory global can be refreshed too.
+    fn persist_session_to_disk(name: String, session: Session) -> Result<SessionStore> {
+        let mut store = SessionStore::load_from_disk();
+        store.upsert(name, session);
+        store.save()?;
+        Ok(store)
+    }
 
-        // Check if the saved layout version is different from the current version
-        // Notify the user and ask if they want to reset the layout to default.
-        if state.version != Some(MAIN_DOCK_AREA.version) {
+    /// Loads the active session (if any) into `dock_area`, applying its
+    /// theme mode and locale too. Returns an error when there is no active
+    /// session. When its layout version is stale, the layout is still
+    /// loaded and the user is asked whether to reset to the default layout.
+    fn load_active_session(dock_area: View<DockArea>, cx: &mut WindowContext) -> Result<()> {
+        let session = SessionStore::global(cx)
+            .active()
+            .cloned()
+            .context("no active session")?;
+
+        if session.layout.version != Some(MAIN_DOCK_AREA.version) {
             let answer = cx.prompt(PromptLevel::Info, "The default main layout has been updated.\nDo you want to reset the layout to default?", None,
                 &["Yes", "No"]);
 
@@ -190,8 +397,11 @@ impl StoryWorkspace {
             .detach();
         }
 
+        Theme::change(session.theme_mode, cx);
+        ui::set_locale(&session.locale);
+
         dock_area.update(cx, |dock_area, cx| {
-            dock_area.load(state, cx).context("load layout")?;
+            dock_area.load(session.layout, cx).context("load layout")?;
 
             Ok::<(), anyhow::Error>(())
         })
@@ -217,7 +427,10 @@ impl StoryWorkspace {
             view.set_bottom_dock(bottom_panels, Some(px(200.)), cx);
             view.set_right_dock(right_panels, Some(px(320.)), cx);
 
-            Self::save_state(&view.dump(cx)).unwrap();
+            let (name, session) = Self::session_snapshot(cx, view.dump(cx), None);
+            if let Ok(store) = Self::persist_session_to_disk(name, session) {
+                cx.set_global(store);
+            }
         });
     }
 
@@ -259,11 +472,19 @@ impl StoryWorkspace {
         app_state: Arc<AppState>,
         cx: &mut AppContext,
     ) -> Task<anyhow::Result<WindowHandle<Root>>> {
-        let window_bounds = Bounds::centered(None, size(px(1600.0), px(1200.0)), cx);
+        // Restore the last-active session's window bounds, falling back to
+        // a window centered at 1600x1200 the first time the app runs.
+        let window_bounds = SessionStore::global(cx)
+            .active()
+            .and_then(|session| session.window_bounds)
+            .map(WindowBounds::from)
+            .unwrap_or_else(|| {
+                WindowBounds::Windowed(Bounds::centered(None, size(px(1600.0), px(1200.0)), cx))
+            });
 
         cx.spawn(|mut cx| async move {
             let options = WindowOptions {
-                window_bounds: Some(WindowBounds::Windowed(window_bounds)),
+                window_bounds: Some(window_bounds),
                 titlebar: Some(TitlebarOptions {
                     title: None,
                     appears_transparent: true,
@@ -329,6 +550,15 @@ impl Render for StoryWorkspace {
             .flex_col()
             .bg(cx.theme().background)
             .text_color(cx.theme().foreground)
+            .on_action(cx.listener(Self::toggle_command_palette))
+            .on_action(cx.listener(Self::select_locale))
+            .on_action(cx.listener(Self::select_theme))
+            .on_action(cx.listener(Self::open_save_layout_modal))
+            .on_action(cx.listener(Self::switch_layout))
+            .on_action(cx.listener(Self::delete_layout))
+            .on_action(cx.listener(Self::confirm_save_layout))
+            .on_action(cx.listener(Self::navigate_back))
+            .on_action(cx.listener(Self::navigate_forward))
             .child(
                 TitleBar::new()
                     // left side
@@ -355,6 +585,8 @@ impl Render for StoryWorkspace {
                                     .on_click(cx.listener(Self::change_color_mode)),
                             )
                             .child(self.locale_selector.clone())
+                            .child(self.theme_selector.clone())
+                            .child(self.layouts_selector.clone())
                             .child(
                                 Button::new("github")
                                     .icon(IconName::GitHub)
@@ -394,7 +626,12 @@ impl Render for StoryWorkspace {
                             ),
                     ),
             )
-            .child(self.dock_area.clone())
+            .child(
+                div()
+                    .flex_1()
+                    .on_focus_in(cx.listener(Self::on_panel_focus_in))
+                    .child(self.dock_area.clone()),
+            )
             .children(drawer_layer)
             .children(modal_layer)
             .child(div().absolute().top_8().children(notification_layer))
@@ -448,3 +685,165 @@ impl Render for LocaleSelector {
             )
     }
 }
+
+struct ThemeSelector {
+    focus_handle: FocusHandle,
+    active_theme: Option<SharedString>,
+}
+
+impl ThemeSelector {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        // Seed from whichever registered theme matches the mode the active
+        // session already applied (via `Theme::change` before this view is
+        // built), so the popup menu's checkmark reflects reality on startup
+        // instead of only appearing after the user picks a theme by hand.
+        // There's no persisted theme *name* to match against exactly (a
+        // `Session` only stores the `ThemeMode`), so this is a best effort:
+        // the first registered theme for that mode wins.
+        let active_theme = ThemeRegistry::global(cx)
+            .themes()
+            .iter()
+            .find(|theme| theme.mode == cx.theme().mode)
+            .map(|theme| theme.name.clone());
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            active_theme,
+        }
+    }
+
+    fn on_select_theme(&mut self, action: &SelectTheme, cx: &mut ViewContext<Self>) {
+        let Some(definition) = ThemeRegistry::global(cx).get(&action.0).cloned() else {
+            return;
+        };
+
+        cx.global_mut::<Theme>().apply_theme(&definition);
+        self.active_theme = Some(action.0.clone());
+        cx.refresh();
+    }
+}
+
+impl Render for ThemeSelector {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let active_theme = self.active_theme.clone();
+
+        div()
+            .id("theme-selector")
+            .track_focus(&focus_handle)
+            .on_action(cx.listener(Self::on_select_theme))
+            .child(
+                Button::new("btn")
+                    .small()
+                    .ghost()
+                    .icon(IconName::Palette)
+                    .popup_menu(move |mut this, cx| {
+                        for theme in ThemeRegistry::global(cx).themes() {
+                            let checked = active_theme.as_deref() == Some(theme.name.as_ref());
+                            this = this.menu_with_check(
+                                theme.name.clone(),
+                                checked,
+                                Box::new(SelectTheme(theme.name.clone())),
+                            );
+                        }
+                        this
+                    })
+                    .anchor(AnchorCorner::TopRight),
+            )
+    }
+}
+
+struct LayoutsSelector {
+    focus_handle: FocusHandle,
+}
+
+impl LayoutsSelector {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+        }
+    }
+}
+
+impl Render for LayoutsSelector {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let store = SessionStore::global(cx);
+        let active_name = store.active_name().map(str::to_string);
+        let names = store.names();
+
+        div()
+            .id("layouts-selector")
+            .track_focus(&focus_handle)
+            .child(
+                Button::new("btn")
+                    .small()
+                    .ghost()
+                    .icon(IconName::LayoutDashboard)
+                    .popup_menu(move |mut this, _| {
+                        for name in &names {
+                            let checked = active_name.as_deref() == Some(name.as_str());
+                            this = this.menu_with_check(
+                                name.clone(),
+                                checked,
+                                Box::new(SwitchLayout(name.clone().into())),
+                            );
+                            this = this.menu(
+                                format!("Delete \"{}\"", name),
+                                Box::new(DeleteLayout(name.clone().into())),
+                            );
+                        }
+                        this.separator()
+                            .menu("Save Current Layout As...", Box::new(SaveLayoutAs))
+                    })
+                    .anchor(AnchorCorner::TopRight),
+            )
+    }
+}
+
+struct SaveLayoutNameModal {
+    focus_handle: FocusHandle,
+    name_input: View<TextInput>,
+}
+
+impl SaveLayoutNameModal {
+    fn new(cx: &mut ViewContext<Self>) -> Self {
+        let name_input = cx.new_view(|cx| TextInput::new(cx).placeholder("Layout name"));
+        Self {
+            focus_handle: cx.focus_handle(),
+            name_input,
+        }
+    }
+
+    fn confirm(&mut self, cx: &mut ViewContext<Self>) {
+        let name = self.name_input.read(cx).text(cx);
+        if !name.is_empty() {
+            cx.dispatch_action(Box::new(ConfirmSaveLayout(name.into())));
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for SaveLayoutNameModal {}
+
+impl FocusableView for SaveLayoutNameModal {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for SaveLayoutNameModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .w(px(360.))
+            .gap_2()
+            .p_2()
+            .child(self.name_input.clone())
+            .child(
+                Button::new("save")
+                    .label("Save")
+                    .on_click(cx.listener(|this, _, cx| this.confirm(cx))),
+            )
+    }
+}
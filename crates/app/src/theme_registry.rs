@@ -0,0 +1,144 @@
+use anyhow::{Context as _, Result};
+use gpui::{AppContext, Global, Hsla, SharedString};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use ui::theme::{Theme, ThemeMode};
+
+use crate::{assets::Assets, paths::config_dir};
+
+const THEMES_DIR: &str = "themes";
+
+/// A complete, named color scheme loaded from a theme JSON file.
+///
+/// Unlike [`Theme::apply_color`] which only overrides `primary`, applying a
+/// [`ThemeDefinition`] replaces the whole palette.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ThemeDefinition {
+    /// Display name shown in the theme selector, e.g. "Dracula".
+    pub name: SharedString,
+    /// Whether this palette should be treated as light or dark.
+    pub mode: ThemeMode,
+    pub background: Hsla,
+    pub foreground: Hsla,
+    pub primary: Hsla,
+    pub accent: Hsla,
+    pub border: Hsla,
+}
+
+/// All themes known to the app: the built-in ones bundled through
+/// [`Assets`] (served from `crates/app/assets/themes/*.json`), plus any
+/// user themes dropped into `<config_dir>/themes/`.
+pub struct ThemeRegistry {
+    themes: Vec<ThemeDefinition>,
+}
+
+impl Global for ThemeRegistry {}
+
+impl ThemeRegistry {
+    /// Loads all themes and installs the registry as a global.
+    pub fn init(cx: &mut AppContext) {
+        let registry = Self::load().unwrap_or_else(|err| {
+            log::error!("failed to load themes: {}", err);
+            Self { themes: Vec::new() }
+        });
+        Self::write_schema_file();
+        cx.set_global(registry);
+    }
+
+    fn load() -> Result<Self> {
+        let mut themes = Self::load_builtin_themes()?;
+        themes.extend(Self::load_user_themes().unwrap_or_default());
+        Ok(Self { themes })
+    }
+
+    fn load_builtin_themes() -> Result<Vec<ThemeDefinition>> {
+        let mut themes = Vec::new();
+        for path in Assets.list(THEMES_DIR)? {
+            if !path.ends_with(".json") {
+                continue;
+            }
+            let Some(bytes) = Assets.load(&path)? else {
+                continue;
+            };
+            themes.push(
+                serde_json::from_slice(&bytes).with_context(|| format!("invalid theme {}", path))?,
+            );
+        }
+        Ok(themes)
+    }
+
+    fn load_user_themes() -> Result<Vec<ThemeDefinition>> {
+        let dir = config_dir().join(THEMES_DIR);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut themes = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let json = std::fs::read_to_string(&path)?;
+            themes.push(
+                serde_json::from_str(&json).with_context(|| format!("invalid theme {:?}", path))?,
+            );
+        }
+        Ok(themes)
+    }
+
+    pub fn global(cx: &AppContext) -> &Self {
+        cx.global::<Self>()
+    }
+
+    /// Registered themes, in load order (built-in first, then user themes).
+    pub fn themes(&self) -> &[ThemeDefinition] {
+        &self.themes
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ThemeDefinition> {
+        self.themes.iter().find(|theme| theme.name.as_ref() == name)
+    }
+
+    /// Best-effort write of [`json_schema`] next to (but outside of) the
+    /// user themes directory, so editors can validate hand-written theme
+    /// files against it. It must live outside `<config_dir>/themes/` itself
+    /// — [`Self::load_user_themes`] scans every `*.json` file in there as a
+    /// theme, and the schema file isn't one.
+    fn write_schema_file() {
+        let path = config_dir().join("theme.schema.json");
+        if let Err(err) = Self::try_write_schema_file(&path) {
+            log::debug!("failed to write theme schema: {}", err);
+        }
+    }
+
+    fn try_write_schema_file(path: &std::path::Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(&json_schema())?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Applies a full [`ThemeDefinition`] on top of the active [`Theme`].
+pub trait ThemeRegistryExt {
+    fn apply_theme(&mut self, definition: &ThemeDefinition);
+}
+
+impl ThemeRegistryExt for Theme {
+    fn apply_theme(&mut self, definition: &ThemeDefinition) {
+        self.mode = definition.mode;
+        self.background = definition.background;
+        self.foreground = definition.foreground;
+        self.primary = definition.primary;
+        self.accent = definition.accent;
+        self.border = definition.border;
+    }
+}
+
+/// JSON schema for theme files, so editors can validate them while authoring.
+pub fn json_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(ThemeDefinition)
+}